@@ -1,20 +1,116 @@
-use crate::transaction_details::TransactionDetails;
+use crate::metrics::Metrics;
+use crate::transaction_details::{InstructionDetails, TransactionDetails};
 use crate::utils;
 
 use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use futures_util::StreamExt;
 use solana_client::client_error::ClientError;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
 use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
 use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::signature::Signature;
-use solana_transaction_status::{EncodedTransaction, UiMessage, UiTransactionEncoding};
+use solana_transaction_status::{
+    EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction, UiTransactionEncoding,
+};
 use tokio::time::sleep;
 
 const SLEEP_DURATION: u64 = 1;
+const SUBSCRIBE_BACKOFF_BASE_MS: u64 = 500;
+const SUBSCRIBE_BACKOFF_MAX_MS: u64 = 30_000;
+/// Subscription attempts that end (with an error or a clean close) after which the subscribe
+/// loop gives up and falls back to polling; the count is never reset, so it covers every
+/// attempt since the loop started
+const SUBSCRIBE_MAX_CONSECUTIVE_FAILURES: u32 = 5;
+const RETRY_BASE_DELAY_MS: u64 = 200;
+const RETRY_MAX_DELAY_MS: u64 = 10_000;
+
+/// An error encountered while fetching or paginating signatures
+///
+/// This wraps the underlying RPC client error alongside the "account not found" case, which
+/// the original implementation used to paper over with an `unwrap()`.
+#[derive(Debug)]
+pub enum AggregatorError {
+    Client(ClientError),
+    AccountNotFound,
+}
+
+impl fmt::Display for AggregatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregatorError::Client(err) => write!(f, "{}", err),
+            AggregatorError::AccountNotFound => write!(f, "monitored account not found"),
+        }
+    }
+}
+
+impl std::error::Error for AggregatorError {}
+
+impl From<ClientError> for AggregatorError {
+    fn from(err: ClientError) -> Self {
+        AggregatorError::Client(err)
+    }
+}
+
+/// Retry an async operation with exponential backoff and jitter
+///
+/// This function retries `f` up to `max_retries` times, doubling the delay after each failure
+/// (capped at `RETRY_MAX_DELAY_MS`) and adding up to 25% random jitter so retrying clients don't
+/// all wake up in lockstep.
+///
+/// # Arguments
+/// * `max_retries` - The maximum number of attempts before giving up
+/// * `f` - The operation to retry, called once per attempt
+///
+/// # Returns
+/// The operation's result, or its last error once `max_retries` attempts have failed
+async fn retry_with_backoff<T, F, Fut>(max_retries: u32, mut f: F) -> Result<T, AggregatorError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, AggregatorError>>,
+{
+    let mut delay_ms = RETRY_BASE_DELAY_MS;
+
+    for attempt in 1..=max_retries.max(1) {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt < max_retries => {
+                let jitter_ms = (rand::random::<f64>() * delay_ms as f64 * 0.25) as u64;
+                tracing::warn!(
+                    "⚠️ Attempt {}/{} failed, retrying in {}ms: {}",
+                    attempt,
+                    max_retries,
+                    delay_ms + jitter_ms,
+                    err
+                );
+                sleep(Duration::from_millis(delay_ms + jitter_ms)).await;
+                delay_ms = (delay_ms * 2).min(RETRY_MAX_DELAY_MS);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    unreachable!("loop always returns on the final attempt")
+}
+
+/// Ingestion mode
+///
+/// This enum represents how the aggregator pulls new transactions from the network.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IngestMode {
+    /// Poll `getSignaturesForAddress` on a fixed interval
+    Poll,
+    /// Subscribe to the pubsub `logsSubscribe` stream, reconnecting with backoff on disconnect
+    /// and falling back to polling after repeated disconnects
+    Subscribe,
+}
 
 /// Solana Aggregator
 ///
@@ -22,10 +118,18 @@ const SLEEP_DURATION: u64 = 1;
 ///
 /// # Fields
 /// * `client` - The RPC client
+/// * `ws_url` - The pubsub WebSocket URL for Solana
 /// * `transactions` - The transactions, stored in a mutexed hash map
+/// * `metrics` - The throughput/latency metrics registry
+/// * `backfill_limit` - How many historical signatures to page through on startup
+/// * `max_retries` - How many attempts each RPC call gets before surfacing an error
 pub struct SolanaAggregator {
     client: RpcClient,
+    ws_url: String,
     transactions: Arc<Mutex<HashMap<Signature, TransactionDetails>>>,
+    metrics: Arc<Metrics>,
+    backfill_limit: u64,
+    max_retries: u32,
 }
 
 impl SolanaAggregator {
@@ -35,40 +139,71 @@ impl SolanaAggregator {
     ///
     /// # Arguments
     /// * `rpc_url` - The RPC URL for Solana
+    /// * `ws_url` - The pubsub WebSocket URL for Solana
     /// * `transactions` - The transactions mutexed hash map
+    /// * `metrics` - The throughput/latency metrics registry
+    /// * `backfill_limit` - How many historical signatures to page through on startup
+    /// * `max_retries` - How many attempts each RPC call gets before surfacing an error
     ///
     /// # Returns
     /// A new Solana aggregator
     pub fn new(
         rpc_url: &str,
+        ws_url: &str,
         transactions: Arc<Mutex<HashMap<Signature, TransactionDetails>>>,
+        metrics: Arc<Metrics>,
+        backfill_limit: u64,
+        max_retries: u32,
     ) -> Self {
         // Create a new RPC client connected to the Solana RPC URL
         let client = RpcClient::new(rpc_url);
 
         SolanaAggregator {
             client,
+            ws_url: ws_url.to_string(),
             transactions,
+            metrics,
+            backfill_limit,
+            max_retries,
         }
     }
 
     /// Fetch transactions
     ///
-    /// This function fetches transactions from the Solana network.
+    /// This function fetches transactions from the Solana network, using either the polling
+    /// or the pubsub subscription ingestion path.
+    ///
+    /// # Arguments
+    /// * `self` - The Solana aggregator
+    /// * `ingest_mode` - Whether to poll for signatures or subscribe to the pubsub stream
+    pub async fn fetch_transactions(&self, ingest_mode: IngestMode) {
+        match ingest_mode {
+            IngestMode::Poll => self.fetch_transactions_poll().await,
+            IngestMode::Subscribe => self.fetch_transactions_subscribe().await,
+        }
+    }
+
+    /// Fetch transactions by polling
+    ///
+    /// This function backfills up to `backfill_limit` historical signatures on startup, then
+    /// polls `getSignaturesForAddress` on a fixed interval using the `until` cursor derived
+    /// from the newest signature of the previous batch, so the cursor no longer depends on
+    /// reading an unordered `HashMap`. Each poll fetches a single page of at most 1000
+    /// signatures; if more than that arrived since the last poll, the older ones are not fetched.
     ///
     /// # Arguments
     /// * `self` - The Solana aggregator
-    pub async fn fetch_transactions(&self) {
-        // Set up the parameters for fetching signatures
-        let mut last_signature: Option<Signature> = None;
+    async fn fetch_transactions_poll(&self) {
+        let mut until_signature = self.backfill().await;
 
         loop {
-            // Fetch the latest signatures for finalized transactions
-            match self.fetch_signatures(last_signature).await {
-                Ok(signatures) => {
+            // Fetch any signatures newer than the last batch's newest signature
+            match self.fetch_signatures(None, until_signature).await {
+                Ok(signatures) if !signatures.is_empty() => {
+                    until_signature = newest_signature(&signatures).or(until_signature);
                     self.process_signatures(signatures).await;
-                    last_signature = self.update_last_signature();
                 }
+                Ok(_) => {}
                 Err(err) => {
                     tracing::error!("‚ùå Error fetching signatures: {}", err);
                 }
@@ -79,39 +214,186 @@ impl SolanaAggregator {
         }
     }
 
-    /// Fetch signatures
+    /// Backfill historical signatures on startup
     ///
-    /// This function fetches signatures from the Solana network.
+    /// This function pages backwards with the `before` cursor, processing each page as it
+    /// arrives, until it has walked `backfill_limit` signatures or runs out of history.
     ///
     /// # Arguments
     /// * `self` - The Solana aggregator
-    /// * `last_signature` - The last signature
     ///
     /// # Returns
-    /// A vector of signatures
-    async fn fetch_signatures(
-        &self,
-        last_signature: Option<Signature>,
-    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>, ClientError> {
+    /// The newest signature seen, to use as the `until` cursor for forward polling
+    async fn backfill(&self) -> Option<Signature> {
+        let mut before_signature: Option<Signature> = None;
+        let mut newest_seen: Option<Signature> = None;
+        let mut backfilled = 0u64;
+
+        while backfilled < self.backfill_limit {
+            match self.fetch_signatures(before_signature, None).await {
+                Ok(signatures) if !signatures.is_empty() => {
+                    if newest_seen.is_none() {
+                        newest_seen = newest_signature(&signatures);
+                    }
+                    backfilled += signatures.len() as u64;
+                    before_signature = signatures
+                        .last()
+                        .and_then(|info| Signature::from_str(&info.signature).ok());
+                    self.process_signatures(signatures).await;
+                }
+                Ok(_) => break,
+                Err(err) => {
+                    tracing::error!("‚ùå Error backfilling signatures: {}", err);
+                    break;
+                }
+            }
+        }
+
+        newest_seen
+    }
+
+    /// Fetch transactions by subscribing to the pubsub logs stream
+    ///
+    /// This function opens a `logsSubscribe` subscription for the monitored account and
+    /// fetches the full transaction for each notification as it arrives. If the subscription
+    /// fails or drops, it reconnects with exponential backoff; once the retries are exhausted
+    /// it falls back to the polling path so ingestion keeps making progress.
+    ///
+    /// # Arguments
+    /// * `self` - The Solana aggregator
+    async fn fetch_transactions_subscribe(&self) {
+        let mut backoff_ms = SUBSCRIBE_BACKOFF_BASE_MS;
+        let mut consecutive_failures = 0u32;
+
+        loop {
+            let subscription_result = self.run_logs_subscription().await;
+
+            if subscription_result.is_ok() {
+                // The stream ended cleanly (server closed it). The backoff restarts from its
+                // base delay, but a reconnect still waits it out, so a server that closes the
+                // subscription immediately is not hammered in a tight loop; the clean close
+                // also counts toward the fallback to polling.
+                backoff_ms = SUBSCRIBE_BACKOFF_BASE_MS;
+            }
+
+            consecutive_failures += 1;
+            if consecutive_failures >= SUBSCRIBE_MAX_CONSECUTIVE_FAILURES {
+                tracing::error!(
+                    "‚ùå Pubsub subscription unavailable after {} attempts ({}), falling back to polling",
+                    consecutive_failures,
+                    subscription_result
+                        .err()
+                        .map(|err| err.to_string())
+                        .unwrap_or_else(|| "stream closed cleanly".to_string())
+                );
+                return self.fetch_transactions_poll().await;
+            }
+
+            match subscription_result {
+                Ok(()) => {
+                    tracing::warn!(
+                        "‚ö†Ô∏è Pubsub subscription closed cleanly, reconnecting in {}ms",
+                        backoff_ms
+                    );
+                }
+                Err(err) => {
+                    tracing::error!(
+                        "‚ùå Pubsub subscription error, reconnecting in {}ms: {}",
+                        backoff_ms,
+                        err
+                    );
+                }
+            }
+            sleep(Duration::from_millis(backoff_ms)).await;
+            backoff_ms = (backoff_ms * 2).min(SUBSCRIBE_BACKOFF_MAX_MS);
+        }
+    }
+
+    /// Run a single pubsub logs subscription until it errors or closes
+    ///
+    /// This function connects to the pubsub WebSocket, subscribes to logs mentioning the
+    /// monitored account, and stores a transaction for every notification received.
+    ///
+    /// # Arguments
+    /// * `self` - The Solana aggregator
+    ///
+    /// # Returns
+    /// `Ok(())` if the stream closed without error, or the error that broke it
+    async fn run_logs_subscription(&self) -> Result<(), Box<dyn std::error::Error>> {
         let account = self
             .client
             .get_account_with_commitment(
-                &self.client.get_identity().unwrap(),
+                &self.client.get_identity()?,
                 CommitmentConfig::finalized(),
-            )
-            .unwrap()
+            )?
             .value
-            .unwrap()
+            .ok_or("monitored account not found")?
             .owner;
-        let config = GetConfirmedSignaturesForAddress2Config {
-            before: None,
-            until: last_signature,
-            limit: Some(1000),
-            commitment: Some(CommitmentConfig::finalized()),
-        };
 
-        self.client
-            .get_signatures_for_address_with_config(&account, config)
+        let pubsub_client = PubsubClient::new(&self.ws_url).await?;
+        let (mut logs, unsubscribe) = pubsub_client
+            .logs_subscribe(
+                RpcTransactionLogsFilter::Mentions(vec![account.to_string()]),
+                RpcTransactionLogsConfig {
+                    commitment: Some(CommitmentConfig::finalized()),
+                },
+            )
+            .await?;
+
+        tracing::info!("üîå Subscribed to logs for account {}", account);
+
+        while let Some(notification) = logs.next().await {
+            let signature = Signature::from_str(&notification.value.signature)?;
+            if let Err(err) = self.fetch_and_process_transaction(&signature).await {
+                tracing::error!("‚ùå Error processing transaction: {}", err);
+            }
+        }
+
+        unsubscribe().await;
+        Ok(())
+    }
+
+    /// Fetch signatures
+    ///
+    /// This function fetches a page of signatures from the Solana network, wrapping the RPC
+    /// calls in `retry_with_backoff` so a transient error doesn't crash or stall the ingestion
+    /// loop the way the previous `.unwrap()`-based implementation did.
+    ///
+    /// # Arguments
+    /// * `self` - The Solana aggregator
+    /// * `before` - Page backwards starting before this signature, for backfill
+    /// * `until` - Stop at this signature, for forward polling since the last batch
+    ///
+    /// # Returns
+    /// A vector of signatures, ordered newest-first
+    async fn fetch_signatures(
+        &self,
+        before: Option<Signature>,
+        until: Option<Signature>,
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>, AggregatorError> {
+        retry_with_backoff(self.max_retries, || async {
+            let account = self
+                .client
+                .get_account_with_commitment(
+                    &self.client.get_identity()?,
+                    CommitmentConfig::finalized(),
+                )?
+                .value
+                .ok_or(AggregatorError::AccountNotFound)?
+                .owner;
+
+            let config = GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: Some(1000),
+                commitment: Some(CommitmentConfig::finalized()),
+            };
+
+            self.client
+                .get_signatures_for_address_with_config(&account, config)
+                .map_err(AggregatorError::from)
+        })
+        .await
     }
 
     /// Process signatures
@@ -149,44 +431,137 @@ impl SolanaAggregator {
         signature: &Signature,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let transaction = self
-            .client
-            .get_transaction(signature, UiTransactionEncoding::Json)?;
+            .metrics
+            .time_get_transaction(|| {
+                self.client
+                    .get_transaction(signature, UiTransactionEncoding::JsonParsed)
+            })?;
 
         let block_time = transaction.block_time.unwrap_or(0);
+        let meta = transaction.transaction.meta;
 
-        if let EncodedTransaction::Json(transaction) = transaction.transaction.transaction {
-            if let UiMessage::Raw(message) = transaction.message {
-                let sender = &message.account_keys[0];
-                let receiver = &message.account_keys[1];
-                let data = &message.instructions[0].data;
-
-                let transaction_details = TransactionDetails {
-                    sender: sender.to_string(),
-                    receiver: receiver.to_string(),
-                    data: data.to_string(),
-                    timestamp: block_time,
-                };
-                tracing::info!(
-                    "üìÑ Storing transaction with signature {} ({})",
-                    signature,
-                    utils::format_time(block_time)
-                );
-                let mut transactions = self.transactions.lock().unwrap();
-                transactions.insert(*signature, transaction_details);
-            }
-        }
+        let (account_keys, instructions) = match transaction.transaction.transaction {
+            EncodedTransaction::Json(transaction) => match transaction.message {
+                UiMessage::Raw(message) => {
+                    let account_keys = message.account_keys;
+                    let instructions = message
+                        .instructions
+                        .into_iter()
+                        .map(|instruction| InstructionDetails {
+                            program_id_index: instruction.program_id_index,
+                            accounts: instruction
+                                .accounts
+                                .iter()
+                                .filter_map(|&index| account_keys.get(index as usize).cloned())
+                                .collect(),
+                            data: instruction.data,
+                            parsed: None,
+                        })
+                        .collect();
+                    (account_keys, instructions)
+                }
+                UiMessage::Parsed(message) => {
+                    let account_keys: Vec<String> = message
+                        .account_keys
+                        .iter()
+                        .map(|account| account.pubkey.clone())
+                        .collect();
+                    let instructions = message
+                        .instructions
+                        .into_iter()
+                        .map(|instruction| match instruction {
+                            UiInstruction::Compiled(instruction) => InstructionDetails {
+                                program_id_index: instruction.program_id_index,
+                                accounts: instruction
+                                    .accounts
+                                    .iter()
+                                    .filter_map(|&index| account_keys.get(index as usize).cloned())
+                                    .collect(),
+                                data: instruction.data,
+                                parsed: None,
+                            },
+                            UiInstruction::Parsed(UiParsedInstruction::Parsed(instruction)) => {
+                                let program_id_index = account_keys
+                                    .iter()
+                                    .position(|key| key == &instruction.program_id)
+                                    .unwrap_or(0) as u8;
+                                InstructionDetails {
+                                    program_id_index,
+                                    accounts: Vec::new(),
+                                    data: String::new(),
+                                    parsed: Some(instruction.parsed),
+                                }
+                            }
+                            UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(
+                                instruction,
+                            )) => {
+                                let program_id_index = account_keys
+                                    .iter()
+                                    .position(|key| key == &instruction.program_id)
+                                    .unwrap_or(0) as u8;
+                                InstructionDetails {
+                                    program_id_index,
+                                    accounts: instruction.accounts,
+                                    data: instruction.data,
+                                    parsed: None,
+                                }
+                            }
+                        })
+                        .collect();
+                    (account_keys, instructions)
+                }
+            },
+            _ => return Ok(()),
+        };
+
+        let fee = meta.as_ref().map(|meta| meta.fee).unwrap_or(0);
+        let success = meta.as_ref().map(|meta| meta.err.is_none()).unwrap_or(true);
+        let err = meta
+            .as_ref()
+            .and_then(|meta| meta.err.as_ref())
+            .map(|err| err.to_string());
+        let compute_units_consumed = meta
+            .as_ref()
+            .and_then(|meta| Option::<u64>::from(meta.compute_units_consumed.clone()));
+        let log_messages = meta
+            .as_ref()
+            .and_then(|meta| Option::<Vec<String>>::from(meta.log_messages.clone()))
+            .unwrap_or_default();
+
+        let transaction_details = TransactionDetails {
+            account_keys,
+            instructions,
+            fee,
+            compute_units_consumed,
+            success,
+            err,
+            log_messages,
+            timestamp: block_time,
+        };
+
+        tracing::info!(
+            "📄 Storing transaction with signature {} ({})",
+            signature,
+            utils::format_time(block_time)
+        );
+        let mut transactions = self.transactions.lock().unwrap();
+        transactions.insert(*signature, transaction_details);
+        drop(transactions);
+        self.metrics.record_ingested();
 
         Ok(())
     }
+}
 
-    /// Update the last signature
-    ///
-    /// This function updates the last signature.
-    ///
-    /// # Arguments
-    /// * `self` - The Solana aggregator
-    fn update_last_signature(&self) -> Option<Signature> {
-        let transactions = self.transactions.lock().unwrap();
-        transactions.keys().last().cloned()
-    }
+/// The newest signature in a batch returned by `getSignaturesForAddress`
+///
+/// The RPC returns signatures newest-first, so this is simply the first entry; pulling the
+/// cursor from here (rather than from an unordered `HashMap` of stored transactions) is what
+/// keeps pagination deterministic.
+fn newest_signature(
+    signatures: &[RpcConfirmedTransactionStatusWithSignature],
+) -> Option<Signature> {
+    signatures
+        .first()
+        .and_then(|info| Signature::from_str(&info.signature).ok())
 }
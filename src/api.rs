@@ -1,35 +1,98 @@
+use crate::cluster::{ClusterTracker, ForwardMode};
+use crate::confirmation::ConfirmationTracker;
+use crate::metrics::Metrics;
 use crate::transaction_details::TransactionDetails;
 
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
 use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
 use warp::Filter;
 
+/// The body of a `POST /transactions` request
+///
+/// # Fields
+/// * `transaction` - The base64- or base58-encoded, signed transaction to submit
+#[derive(Deserialize, Debug)]
+struct SubmitTransactionRequest {
+    transaction: String,
+}
+
+/// The response to a `POST /transactions` request
+///
+/// # Fields
+/// * `signature` - The signature of the submitted transaction
+#[derive(Serialize, Debug)]
+struct SubmitTransactionResponse {
+    signature: String,
+}
+
+/// The response to a `GET /transactions?id=<sig>&status=true` request
+///
+/// # Fields
+/// * `signature` - The signature that was queried
+/// * `status` - The current commitment status
+/// * `slot` - The slot the transaction was last observed at, if any
+/// * `err` - The transaction error, if it failed on-chain
+#[derive(Serialize, Debug)]
+struct TransactionStatusResponse {
+    signature: String,
+    status: Option<crate::confirmation::TxStatus>,
+    slot: Option<u64>,
+    err: Option<String>,
+}
+
 /// Create the RESTful API
 ///
 /// This function creates the RESTful API.
 ///
 /// # Arguments
 /// * `transactions` - The transactions hash map
+/// * `client` - The RPC client used to submit transactions
+/// * `confirmation_tracker` - The confirmation tracker used to record and poll submitted transactions
+/// * `metrics` - The throughput/latency metrics registry
+/// * `cluster_tracker` - The cluster tracker used for direct TPU forwarding
+/// * `forward_mode` - Whether to submit transactions through RPC or forward them to the TPU
 ///
 /// # Returns
 /// A warp filter
 pub fn create_api(
     transactions: Arc<Mutex<HashMap<Signature, TransactionDetails>>>,
+    client: Arc<RpcClient>,
+    confirmation_tracker: Arc<ConfirmationTracker>,
+    metrics: Arc<Metrics>,
+    cluster_tracker: Arc<ClusterTracker>,
+    forward_mode: ForwardMode,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-    warp::path("transactions")
+    let get_transactions = warp::get()
+        .and(warp::path("transactions"))
         .and(warp::query::<HashMap<String, String>>())
         .and(with_transactions(transactions))
+        .and(with_confirmation_tracker(confirmation_tracker.clone()))
         .map(
             |params: HashMap<String, String>,
-             transactions: Arc<Mutex<HashMap<Signature, TransactionDetails>>>| {
-                let transactions = transactions.lock().unwrap();
-
+             transactions: Arc<Mutex<HashMap<Signature, TransactionDetails>>>,
+             confirmation_tracker: Arc<ConfirmationTracker>| {
                 if let Some(signature) = params.get("id") {
                     let signature =
                         Signature::from_str(signature).expect("Invalid signature format");
+
+                    if params.get("status").map(String::as_str) == Some("true") {
+                        let pending = confirmation_tracker.get(&signature);
+                        return warp::reply::json(&TransactionStatusResponse {
+                            signature: signature.to_string(),
+                            status: pending.as_ref().map(|pending| pending.status),
+                            slot: pending.as_ref().and_then(|pending| pending.slot),
+                            err: pending.and_then(|pending| pending.err),
+                        });
+                    }
+
+                    let transactions = transactions.lock().unwrap();
                     if let Some(transaction) = transactions.get(&signature) {
                         return warp::reply::json(transaction);
                     } else {
@@ -40,7 +103,8 @@ pub fn create_api(
                 if let Some(day) = params.get("day") {
                     let date = chrono::NaiveDate::parse_from_str(day, "%d/%m/%Y")
                         .expect("Invalid date format");
-                    let transactions_for_day: Vec<&TransactionDetails> = transactions
+                    let transactions = transactions.lock().unwrap();
+                    let transactions_for_day: Vec<TransactionDetails> = transactions
                         .values()
                         .filter(|transaction| {
                             chrono::DateTime::from_timestamp(transaction.timestamp, 0)
@@ -48,13 +112,102 @@ pub fn create_api(
                                 .date_naive()
                                 == date
                         })
+                        .cloned()
                         .collect();
                     return warp::reply::json(&transactions_for_day);
                 }
 
                 warp::reply::json(&"Invalid query parameters")
             },
-        )
+        );
+
+    let submit_transaction = warp::post()
+        .and(warp::path("transactions"))
+        .and(warp::body::json())
+        .and(with_client(client))
+        .and(with_confirmation_tracker(confirmation_tracker))
+        .and(with_cluster_tracker(cluster_tracker))
+        .then(
+            move |request: SubmitTransactionRequest,
+                  client: Arc<RpcClient>,
+                  confirmation_tracker: Arc<ConfirmationTracker>,
+                  cluster_tracker: Arc<ClusterTracker>| async move {
+                let decoded = match base64::engine::general_purpose::STANDARD
+                    .decode(&request.transaction)
+                    .or_else(|_| bs58::decode(&request.transaction).into_vec())
+                {
+                    Ok(decoded) => decoded,
+                    Err(_) => {
+                        return warp::reply::json(&"Invalid transaction encoding");
+                    }
+                };
+
+                let transaction: VersionedTransaction = match bincode::deserialize(&decoded) {
+                    Ok(transaction) => transaction,
+                    Err(_) => {
+                        return warp::reply::json(&"Invalid transaction payload");
+                    }
+                };
+
+                // Both arms drive a blocking network call (QUIC send or the blocking RPC
+                // client), which must not run directly on the Tokio worker thread, so hand
+                // each off to a blocking-pool thread.
+                let result: Result<Signature, String> = match forward_mode {
+                    ForwardMode::Rpc => tokio::task::spawn_blocking(move || {
+                        client
+                            .send_transaction(&transaction)
+                            .map_err(|err| err.to_string())
+                    })
+                    .await
+                    .map_err(|err| err.to_string())
+                    .and_then(|result| result),
+                    ForwardMode::Tpu => tokio::task::spawn_blocking(move || {
+                        cluster_tracker
+                            .forward_transaction(&transaction)
+                            .map_err(|err| err.to_string())
+                            .and_then(|()| {
+                                transaction
+                                    .signatures
+                                    .first()
+                                    .copied()
+                                    .ok_or_else(|| "transaction has no signatures".to_string())
+                            })
+                    })
+                    .await
+                    .map_err(|err| err.to_string())
+                    .and_then(|result| result),
+                };
+
+                match result {
+                    Ok(signature) => {
+                        let _ = tokio::task::spawn_blocking(move || {
+                            confirmation_tracker.track(signature)
+                        })
+                        .await;
+                        warp::reply::json(&SubmitTransactionResponse {
+                            signature: signature.to_string(),
+                        })
+                    }
+                    Err(err) => {
+                        tracing::error!("‚ùå Error sending transaction: {}", err);
+                        warp::reply::json(&"Error sending transaction")
+                    }
+                }
+            },
+        );
+
+    let get_metrics = warp::get()
+        .and(warp::path("metrics"))
+        .and(with_metrics(metrics))
+        .map(|metrics: Arc<Metrics>| {
+            warp::reply::with_header(
+                metrics.render_prometheus(),
+                "content-type",
+                "text/plain; version=0.0.4",
+            )
+        });
+
+    get_transactions.or(submit_transaction).or(get_metrics)
 }
 
 /// Return a mutable reference to the transactions hash map
@@ -75,6 +228,66 @@ fn with_transactions(
     warp::any().map(move || transactions.clone())
 }
 
+/// Return a clone of the RPC client
+///
+/// This function returns a clone of the shared RPC client.
+///
+/// # Arguments
+/// * `client` - The RPC client
+///
+/// # Returns
+/// A warp filter
+fn with_client(
+    client: Arc<RpcClient>,
+) -> impl Filter<Extract = (Arc<RpcClient>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || client.clone())
+}
+
+/// Return a clone of the confirmation tracker
+///
+/// This function returns a clone of the shared confirmation tracker.
+///
+/// # Arguments
+/// * `confirmation_tracker` - The confirmation tracker
+///
+/// # Returns
+/// A warp filter
+fn with_confirmation_tracker(
+    confirmation_tracker: Arc<ConfirmationTracker>,
+) -> impl Filter<Extract = (Arc<ConfirmationTracker>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || confirmation_tracker.clone())
+}
+
+/// Return a clone of the metrics registry
+///
+/// This function returns a clone of the shared metrics registry.
+///
+/// # Arguments
+/// * `metrics` - The metrics registry
+///
+/// # Returns
+/// A warp filter
+fn with_metrics(
+    metrics: Arc<Metrics>,
+) -> impl Filter<Extract = (Arc<Metrics>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || metrics.clone())
+}
+
+/// Return a clone of the cluster tracker
+///
+/// This function returns a clone of the shared cluster tracker.
+///
+/// # Arguments
+/// * `cluster_tracker` - The cluster tracker
+///
+/// # Returns
+/// A warp filter
+fn with_cluster_tracker(
+    cluster_tracker: Arc<ClusterTracker>,
+) -> impl Filter<Extract = (Arc<ClusterTracker>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || cluster_tracker.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,6 +295,18 @@ mod tests {
     use std::sync::{Arc, Mutex};
     use warp::test::request;
 
+    fn test_client() -> Arc<RpcClient> {
+        Arc::new(RpcClient::new("http://localhost:8899".to_string()))
+    }
+
+    fn test_confirmation_tracker() -> Arc<ConfirmationTracker> {
+        Arc::new(ConfirmationTracker::new(test_client()))
+    }
+
+    fn test_cluster_tracker() -> Arc<ClusterTracker> {
+        Arc::new(ClusterTracker::new(test_client()))
+    }
+
     #[tokio::test]
     async fn test_get_transaction_by_id() {
         let mut transactions = HashMap::new();
@@ -89,15 +314,26 @@ mod tests {
         transactions.insert(
             signature,
             TransactionDetails {
-                sender: "SenderPubkey".to_string(),
-                receiver: "ReceiverPubkey".to_string(),
-                data: "some_data".to_string(),
+                account_keys: vec!["SenderPubkey".to_string(), "ReceiverPubkey".to_string()],
+                instructions: vec![],
+                fee: 5000,
+                compute_units_consumed: None,
+                success: true,
+                err: None,
+                log_messages: vec![],
                 timestamp: 1620000000,
             },
         );
 
         let transactions = Arc::new(Mutex::new(transactions));
-        let api = create_api(transactions);
+        let api = create_api(
+            transactions,
+            test_client(),
+            test_confirmation_tracker(),
+            Arc::new(Metrics::new()),
+            test_cluster_tracker(),
+            ForwardMode::Rpc,
+        );
 
         let response = request()
             .path(&format!("/transactions?id={}", signature))
@@ -113,7 +349,14 @@ mod tests {
     async fn test_get_transaction_by_id_not_found() {
         let signature = Signature::new_unique();
         let transactions = Arc::new(Mutex::new(HashMap::new()));
-        let api = create_api(transactions);
+        let api = create_api(
+            transactions,
+            test_client(),
+            test_confirmation_tracker(),
+            Arc::new(Metrics::new()),
+            test_cluster_tracker(),
+            ForwardMode::Rpc,
+        );
 
         let response = request()
             .path(&format!("/transactions?id={}", signature))
@@ -128,7 +371,14 @@ mod tests {
     #[tokio::test]
     async fn test_invalid_query_parameters() {
         let transactions = Arc::new(Mutex::new(HashMap::new()));
-        let api = create_api(transactions);
+        let api = create_api(
+            transactions,
+            test_client(),
+            test_confirmation_tracker(),
+            Arc::new(Metrics::new()),
+            test_cluster_tracker(),
+            ForwardMode::Rpc,
+        );
 
         let response = request().path("/transactions").reply(&api).await;
 
@@ -137,4 +387,27 @@ mod tests {
         println!("{}", body_str);
         assert_eq!(body_str, "\"Invalid query parameters\"");
     }
+
+    #[tokio::test]
+    async fn test_get_transaction_status_not_tracked() {
+        let signature = Signature::new_unique();
+        let transactions = Arc::new(Mutex::new(HashMap::new()));
+        let api = create_api(
+            transactions,
+            test_client(),
+            test_confirmation_tracker(),
+            Arc::new(Metrics::new()),
+            test_cluster_tracker(),
+            ForwardMode::Rpc,
+        );
+
+        let response = request()
+            .path(&format!("/transactions?id={}&status=true", signature))
+            .reply(&api)
+            .await;
+
+        assert_eq!(response.status(), 200);
+        let body_str = std::str::from_utf8(response.body()).unwrap();
+        assert!(body_str.contains("\"status\":null"));
+    }
 }
@@ -0,0 +1,221 @@
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use solana_client::connection_cache::ConnectionCache;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use tokio::time::sleep;
+
+/// How often the cluster map is refreshed, in seconds
+const REFRESH_INTERVAL_SECONDS: u64 = 2;
+
+/// How many upcoming leaders to forward a transaction to
+const FORWARD_LEADER_COUNT: usize = 4;
+
+/// Base delay for the cluster-refresh retry backoff, in milliseconds
+const REFRESH_BACKOFF_BASE_MS: u64 = 500;
+
+/// Cap for the cluster-refresh retry backoff, in milliseconds
+const REFRESH_BACKOFF_MAX_MS: u64 = 10_000;
+
+/// Transaction forwarding mode
+///
+/// This enum represents how signed transactions submitted through `POST /transactions` are
+/// sent to the network.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForwardMode {
+    /// Submit through the JSON-RPC `send_transaction` method
+    Rpc,
+    /// Forward directly to the upcoming leaders' TPUs over QUIC
+    Tpu,
+}
+
+/// The slot/leader/TPU-socket state the cluster tracker maintains
+///
+/// # Fields
+/// * `current_slot` - The most recently observed slot
+/// * `leader_schedule` - The leader's identity pubkey for each known slot
+/// * `tpu_sockets` - The TPU-QUIC socket address for each known validator identity
+#[derive(Default)]
+struct ClusterState {
+    current_slot: u64,
+    leader_schedule: HashMap<u64, Pubkey>,
+    tpu_sockets: HashMap<Pubkey, SocketAddr>,
+}
+
+/// Cluster tracker
+///
+/// This struct maintains a slot -> leader -> TPU-socket map by periodically polling
+/// `get_cluster_nodes` and `get_leader_schedule`, and pools QUIC connections to the upcoming
+/// leaders so transactions can be forwarded directly to them instead of through RPC.
+///
+/// # Fields
+/// * `client` - The RPC client used to refresh the cluster map
+/// * `connection_cache` - The pooled QUIC connections to leader TPUs
+/// * `state` - The current slot/leader/TPU-socket map
+pub struct ClusterTracker {
+    client: Arc<RpcClient>,
+    connection_cache: ConnectionCache,
+    state: Mutex<ClusterState>,
+}
+
+impl ClusterTracker {
+    /// Create a new cluster tracker
+    ///
+    /// This function creates a new cluster tracker.
+    ///
+    /// # Arguments
+    /// * `client` - The RPC client used to refresh the cluster map
+    ///
+    /// # Returns
+    /// A new cluster tracker
+    pub fn new(client: Arc<RpcClient>) -> Self {
+        ClusterTracker {
+            client,
+            connection_cache: ConnectionCache::new("solana-tx-aggregator"),
+            state: Mutex::new(ClusterState::default()),
+        }
+    }
+
+    /// Run the cluster refresh loop
+    ///
+    /// This function polls `get_cluster_nodes` and `get_leader_schedule` on a fixed interval,
+    /// retrying with exponential backoff on failure, and updates the slot/leader/TPU-socket map.
+    ///
+    /// # Arguments
+    /// * `self` - The cluster tracker
+    pub async fn run(&self) {
+        let mut backoff_ms = REFRESH_BACKOFF_BASE_MS;
+
+        loop {
+            match self.refresh().await {
+                Ok(()) => {
+                    backoff_ms = REFRESH_BACKOFF_BASE_MS;
+                    sleep(Duration::from_secs(REFRESH_INTERVAL_SECONDS)).await;
+                }
+                Err(err) => {
+                    tracing::error!(
+                        "‚ùå Error refreshing cluster map, retrying in {}ms: {}",
+                        backoff_ms,
+                        err
+                    );
+                    sleep(Duration::from_millis(backoff_ms)).await;
+                    backoff_ms = (backoff_ms * 2).min(REFRESH_BACKOFF_MAX_MS);
+                }
+            }
+        }
+    }
+
+    /// Refresh the slot/leader/TPU-socket map once
+    ///
+    /// # Arguments
+    /// * `self` - The cluster tracker
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or the RPC error that prevented the refresh
+    async fn refresh(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let current_slot = self.client.get_slot()?;
+        let epoch_info = self.client.get_epoch_info()?;
+        let epoch_start_slot = epoch_info.absolute_slot - epoch_info.slot_index;
+
+        let tpu_sockets: HashMap<Pubkey, SocketAddr> = self
+            .client
+            .get_cluster_nodes()?
+            .into_iter()
+            .filter_map(|node| {
+                let pubkey = Pubkey::from_str(&node.pubkey).ok()?;
+                let tpu_quic = node.tpu_quic.or(node.tpu)?;
+                Some((pubkey, tpu_quic))
+            })
+            .collect();
+
+        let leader_schedule: HashMap<u64, Pubkey> = self
+            .client
+            .get_leader_schedule(None)?
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(identity, slot_indexes)| {
+                let identity = Pubkey::from_str(&identity).ok()?;
+                Some(
+                    slot_indexes
+                        .into_iter()
+                        .map(move |slot_index| (epoch_start_slot + slot_index as u64, identity)),
+                )
+            })
+            .flatten()
+            .collect();
+
+        let mut state = self.state.lock().unwrap();
+        state.current_slot = current_slot;
+        state.tpu_sockets = tpu_sockets;
+        state.leader_schedule = leader_schedule;
+
+        Ok(())
+    }
+
+    /// The TPU socket addresses of the next few upcoming leaders
+    ///
+    /// # Arguments
+    /// * `self` - The cluster tracker
+    ///
+    /// # Returns
+    /// Up to `FORWARD_LEADER_COUNT` TPU socket addresses, nearest leader first
+    fn upcoming_leader_tpu_addrs(&self) -> Vec<SocketAddr> {
+        let state = self.state.lock().unwrap();
+        let mut seen_leaders = HashSet::new();
+        (state.current_slot..state.current_slot + FORWARD_LEADER_COUNT as u64 * 4)
+            .filter_map(|slot| state.leader_schedule.get(&slot))
+            // Each leader holds 4 consecutive slots, so dedup by identity to actually walk
+            // the upcoming *leaders* rather than repeating the current one 4 times.
+            .filter(|identity| seen_leaders.insert(**identity))
+            .filter_map(|identity| state.tpu_sockets.get(identity))
+            .copied()
+            .take(FORWARD_LEADER_COUNT)
+            .collect()
+    }
+
+    /// Forward a signed transaction directly to the upcoming leaders' TPUs over QUIC
+    ///
+    /// This function serializes the transaction and fires it at each of the next few leaders'
+    /// TPU sockets using the pooled QUIC connections, bypassing the JSON-RPC `send_transaction`
+    /// path entirely. Confirmation still flows through the existing status-tracking worker.
+    ///
+    /// # Arguments
+    /// * `self` - The cluster tracker
+    /// * `transaction` - The signed transaction to forward
+    ///
+    /// # Returns
+    /// `Ok(())` if the transaction was handed to at least one leader connection
+    pub fn forward_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let wire_transaction = bincode::serialize(transaction)?;
+        let addrs = self.upcoming_leader_tpu_addrs();
+
+        if addrs.is_empty() {
+            return Err("no upcoming leader TPU addresses known yet".into());
+        }
+
+        let mut sent_to_any = false;
+        for addr in addrs {
+            let connection = self.connection_cache.get_connection(&addr);
+            match connection.send_data(&wire_transaction) {
+                Ok(()) => sent_to_any = true,
+                Err(err) => {
+                    tracing::error!("‚ùå Error forwarding transaction to {}: {}", addr, err);
+                }
+            }
+        }
+
+        if !sent_to_any {
+            return Err("failed to forward transaction to any upcoming leader".into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::signature::Signature;
+use tokio::time::sleep;
+
+/// How often the confirmation worker polls `get_signature_statuses`, in seconds
+const POLL_INTERVAL_SECONDS: u64 = 1;
+
+/// How many slots may pass after submission, with the RPC reporting no status for the
+/// signature, before a pending transaction is dropped
+const MAX_PENDING_SLOTS: u64 = 150;
+
+/// The maximum number of signatures `get_signature_statuses` accepts in a single RPC call
+const GET_SIGNATURE_STATUSES_BATCH_LIMIT: usize = 256;
+
+/// Transaction commitment status
+///
+/// This enum mirrors the commitment levels a submitted transaction moves through, plus a
+/// terminal `Dropped` state for transactions whose blockhash expired before confirmation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TxStatus {
+    Processed,
+    Confirmed,
+    Finalized,
+    Dropped,
+}
+
+/// Pending transaction
+///
+/// This struct represents the tracked state of a transaction that was submitted through the
+/// relay, from the slot it was first seen at through to its latest known commitment.
+///
+/// # Fields
+/// * `status` - The current commitment status
+/// * `slot` - The slot the transaction was last observed at, if any
+/// * `err` - The transaction error, if it failed on-chain
+/// * `submitted_slot` - The slot the transaction was submitted at, used for expiry
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PendingTransaction {
+    pub status: TxStatus,
+    pub slot: Option<u64>,
+    pub err: Option<String>,
+    submitted_slot: u64,
+}
+
+/// Confirmation tracker
+///
+/// This struct tracks the commitment status of transactions submitted through the relay,
+/// advancing each entry from `processed` through `confirmed` to `finalized` (or `dropped`)
+/// as the background confirmation worker polls the RPC for their statuses.
+///
+/// # Fields
+/// * `client` - The RPC client used to poll signature statuses
+/// * `table` - The in-memory confirmation table, keyed by signature
+pub struct ConfirmationTracker {
+    client: Arc<RpcClient>,
+    table: Arc<Mutex<HashMap<Signature, PendingTransaction>>>,
+}
+
+impl ConfirmationTracker {
+    /// Create a new confirmation tracker
+    ///
+    /// This function creates a new confirmation tracker.
+    ///
+    /// # Arguments
+    /// * `client` - The RPC client used to poll signature statuses
+    ///
+    /// # Returns
+    /// A new confirmation tracker
+    pub fn new(client: Arc<RpcClient>) -> Self {
+        ConfirmationTracker {
+            client,
+            table: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Track a newly submitted transaction
+    ///
+    /// This function records a transaction as `processed` right after it has been sent, so the
+    /// confirmation worker picks it up on its next pass.
+    ///
+    /// # Arguments
+    /// * `self` - The confirmation tracker
+    /// * `signature` - The signature of the submitted transaction
+    pub fn track(&self, signature: Signature) {
+        let submitted_slot = self.client.get_slot().unwrap_or(0);
+        let mut table = self.table.lock().unwrap();
+        table.insert(
+            signature,
+            PendingTransaction {
+                status: TxStatus::Processed,
+                slot: None,
+                err: None,
+                submitted_slot,
+            },
+        );
+    }
+
+    /// Get the tracked status of a transaction
+    ///
+    /// This function returns the current commitment status of a tracked transaction.
+    ///
+    /// # Arguments
+    /// * `self` - The confirmation tracker
+    /// * `signature` - The signature to look up
+    ///
+    /// # Returns
+    /// The pending transaction entry, if the signature is tracked
+    pub fn get(&self, signature: &Signature) -> Option<PendingTransaction> {
+        let table = self.table.lock().unwrap();
+        table.get(signature).cloned()
+    }
+
+    /// Run the confirmation worker
+    ///
+    /// This function polls all pending signatures through `get_signature_statuses` on a fixed
+    /// interval, chunking them into batches of at most `GET_SIGNATURE_STATUSES_BATCH_LIMIT`
+    /// (the RPC's per-call cap), advances each entry's commitment level, and marks as `dropped`
+    /// entries that still have no status more than `MAX_PENDING_SLOTS` slots after the slot
+    /// they were submitted at. The age is counted from `submitted_slot`; the transaction's
+    /// blockhash is not inspected.
+    ///
+    /// # Arguments
+    /// * `self` - The confirmation tracker
+    pub async fn run(&self) {
+        loop {
+            sleep(Duration::from_secs(POLL_INTERVAL_SECONDS)).await;
+
+            let pending_signatures: Vec<Signature> = {
+                let table = self.table.lock().unwrap();
+                table
+                    .iter()
+                    .filter(|(_, tx)| {
+                        !matches!(tx.status, TxStatus::Finalized | TxStatus::Dropped)
+                    })
+                    .map(|(signature, _)| *signature)
+                    .collect()
+            };
+
+            if pending_signatures.is_empty() {
+                continue;
+            }
+
+            let current_slot = match self.client.get_slot() {
+                Ok(slot) => slot,
+                Err(err) => {
+                    tracing::error!("‚ùå Error fetching current slot: {}", err);
+                    continue;
+                }
+            };
+
+            for chunk in pending_signatures.chunks(GET_SIGNATURE_STATUSES_BATCH_LIMIT) {
+                match self.client.get_signature_statuses(chunk) {
+                    Ok(response) => {
+                        let mut table = self.table.lock().unwrap();
+                        for (signature, status) in chunk.iter().zip(response.value) {
+                            let Some(entry) = table.get_mut(signature) else {
+                                continue;
+                            };
+
+                            if let Some(status) = status {
+                                entry.slot = Some(status.slot);
+                                entry.err = status.err.as_ref().map(|err| err.to_string());
+                                entry.status = match status.confirmation_status {
+                                    Some(solana_transaction_status::TransactionConfirmationStatus::Processed) => {
+                                        TxStatus::Processed
+                                    }
+                                    Some(solana_transaction_status::TransactionConfirmationStatus::Confirmed) => {
+                                        TxStatus::Confirmed
+                                    }
+                                    Some(solana_transaction_status::TransactionConfirmationStatus::Finalized) => {
+                                        TxStatus::Finalized
+                                    }
+                                    None => entry.status,
+                                };
+                            } else if current_slot.saturating_sub(entry.submitted_slot) > MAX_PENDING_SLOTS {
+                                entry.status = TxStatus::Dropped;
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        tracing::error!("‚ùå Error fetching signature statuses: {}", err);
+                    }
+                }
+            }
+        }
+    }
+}
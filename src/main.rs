@@ -1,18 +1,26 @@
 mod aggregator;
+mod api;
+mod cluster;
+mod confirmation;
+mod metrics;
 mod transaction_details;
 mod utils;
 
-use aggregator::SolanaAggregator;
+use aggregator::{IngestMode, SolanaAggregator};
+use cluster::{ClusterTracker, ForwardMode};
+use confirmation::ConfirmationTracker;
+use metrics::Metrics;
 use transaction_details::TransactionDetails;
 
 use std::{
-    collections::HashMap, net::SocketAddr, str::FromStr, sync::{Arc, Mutex}
+    collections::HashMap, net::SocketAddr, path::PathBuf, sync::{Arc, Mutex}
 };
 
 use clap::Parser;
+use solana_client::rpc_client::RpcClient;
 use solana_sdk::signature::Signature;
+use tokio::time::{sleep, Duration};
 use url::Url;
-use warp::Filter;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -24,6 +32,30 @@ struct Args {
     /// The address of this local RESTful API server
     #[arg(short, long, default_value = "0.0.0.0:8080")]
     local_address: String,
+
+    /// The pubsub WebSocket URL for Solana, used when `ingest_mode` is `subscribe`
+    #[arg(long, default_value = "wss://api.devnet.solana.com/")]
+    ws_url: String,
+
+    /// Whether to poll for new signatures or subscribe to the pubsub logs stream
+    #[arg(long, value_enum, default_value = "poll")]
+    ingest_mode: IngestMode,
+
+    /// If set, append one CSV row of metrics (timestamp,tps,p50,p99,total) per second to this path
+    #[arg(long)]
+    metrics_csv: Option<PathBuf>,
+
+    /// Whether to submit transactions through RPC or forward them directly to the upcoming leaders' TPUs
+    #[arg(long, value_enum, default_value = "rpc")]
+    forward_mode: ForwardMode,
+
+    /// How many historical signatures to page through on startup before switching to forward polling
+    #[arg(long, default_value_t = 1000)]
+    backfill_limit: u64,
+
+    /// How many attempts each RPC call gets, with exponential backoff between attempts, before surfacing an error
+    #[arg(long, default_value_t = 5)]
+    max_retries: u32,
 }
 
 #[tokio::main]
@@ -48,64 +80,101 @@ async fn main() {
     let transactions_hash: HashMap<Signature, TransactionDetails> = HashMap::new();
     let transactions = Arc::new(Mutex::new(transactions_hash));
 
+    // Create a new throughput/latency metrics registry
+    let metrics = Arc::new(Metrics::new());
+
     // Create a new Solana aggregator
-    let aggregator = SolanaAggregator::new(rpc_url.as_str(), Arc::clone(&transactions));
+    let aggregator = SolanaAggregator::new(
+        rpc_url.as_str(),
+        &args.ws_url,
+        Arc::clone(&transactions),
+        Arc::clone(&metrics),
+        args.backfill_limit,
+        args.max_retries,
+    );
 
     // Fetch transactions in the background
+    let ingest_mode = args.ingest_mode;
     tokio::spawn(async move {
-        aggregator.fetch_transactions().await;
+        aggregator.fetch_transactions(ingest_mode).await;
     });
 
-    let transactions_filter = warp::path("transactions")
-        .and(warp::query::<HashMap<String, String>>())
-        .and(with_transactions(transactions))
-        .map(
-            |params: HashMap<String, String>,
-             transactions: Arc<Mutex<HashMap<Signature, TransactionDetails>>>| {
-                let transactions = transactions.lock().unwrap();
-
-                if let Some(signature) = params.get("id") {
-                    let signature =
-                        Signature::from_str(signature).expect("Invalid signature format");
-                    if let Some(transaction) = transactions.get(&signature) {
-                        return warp::reply::json(transaction);
-                    }
-                }
-
-                if let Some(day) = params.get("day") {
-                    let date = chrono::NaiveDate::parse_from_str(day, "%d/%m/%Y")
-                        .expect("Invalid date format");
-                    let transactions_for_day: Vec<&TransactionDetails> = transactions
-                        .values()
-                        .filter(|transaction| {
-                            chrono::NaiveDateTime::from_timestamp(transaction.timestamp, 0).date()
-                                == date
-                        })
-                        .collect();
-                    return warp::reply::json(&transactions_for_day);
-                }
-
-                warp::reply::json(&"Invalid query parameters")
-            },
-        );
-
-    warp::serve(transactions_filter).run(local_address).await;
+    // Create a shared RPC client for the send/confirm relay
+    let client = Arc::new(RpcClient::new(rpc_url.as_str().to_string()));
+
+    // Track the commitment status of transactions submitted through the relay
+    let confirmation_tracker = Arc::new(ConfirmationTracker::new(Arc::clone(&client)));
+    let confirmation_worker = Arc::clone(&confirmation_tracker);
+    tokio::spawn(async move {
+        confirmation_worker.run().await;
+    });
+
+    // Append a row of metrics to the CSV export path once a second, if configured
+    if let Some(metrics_csv) = args.metrics_csv {
+        let metrics = Arc::clone(&metrics);
+        tokio::spawn(async move {
+            export_metrics_csv(metrics, metrics_csv).await;
+        });
+    }
+
+    // Track the slot/leader/TPU-socket map used for direct TPU forwarding
+    let cluster_tracker = Arc::new(ClusterTracker::new(Arc::clone(&client)));
+    let cluster_refresher = Arc::clone(&cluster_tracker);
+    tokio::spawn(async move {
+        cluster_refresher.run().await;
+    });
+
+    let api = api::create_api(
+        transactions,
+        client,
+        confirmation_tracker,
+        metrics,
+        cluster_tracker,
+        args.forward_mode,
+    );
+
+    warp::serve(api).run(local_address).await;
 }
 
-/// Return a mutable reference to the transactions hash map
-/// 
-/// This function returns a mutable reference to the transactions hash map.
-fn with_transactions(
-    transactions: Arc<Mutex<HashMap<Signature, TransactionDetails>>>,
-) -> impl Filter<
-    Extract = (Arc<Mutex<HashMap<Signature, TransactionDetails>>>,),
-    Error = std::convert::Infallible,
-> + Clone {
-    warp::any().map(move || transactions.clone())
+/// Append one row of metrics to `path` every second
+///
+/// This function writes the CSV header if the file doesn't exist yet, then appends one
+/// `timestamp,tps,p50,p99,total` row per second so runs can be compared offline.
+///
+/// # Arguments
+/// * `metrics` - The metrics registry to sample
+/// * `path` - The CSV file to append to
+async fn export_metrics_csv(metrics: Arc<Metrics>, path: PathBuf) {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    let write_header = !path.exists();
+    let file = OpenOptions::new().create(true).append(true).open(&path);
+    let mut file = match file {
+        Ok(file) => file,
+        Err(err) => {
+            tracing::error!("‚ùå Error opening metrics CSV file {:?}: {}", path, err);
+            return;
+        }
+    };
+
+    if write_header {
+        if let Err(err) = writeln!(file, "{}", Metrics::csv_header()) {
+            tracing::error!("‚ùå Error writing metrics CSV header: {}", err);
+            return;
+        }
+    }
+
+    loop {
+        sleep(Duration::from_secs(1)).await;
+        if let Err(err) = writeln!(file, "{}", metrics.render_csv_row()) {
+            tracing::error!("‚ùå Error writing metrics CSV row: {}", err);
+        }
+    }
 }
 
 /// Initialize tracing
-/// 
+///
 /// This function initializes the tracing subscriber.
 pub fn init_tracing() {
     use tracing::level_filters::LevelFilter;
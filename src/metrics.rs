@@ -0,0 +1,274 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How many per-second buckets the TPS counter keeps, i.e. the size of the sliding window
+const TPS_WINDOW_SECONDS: usize = 60;
+
+/// The number of exponential latency buckets, spanning `1ms*2^k` for `k` in `0..LATENCY_BUCKETS`
+///
+/// With a 1ms base this covers up to `1ms * 2^13` = ~8.2s.
+const LATENCY_BUCKETS: usize = 14;
+
+/// A sliding-window throughput counter
+///
+/// This struct tracks how many transactions were ingested per second over a rolling window,
+/// using a ring buffer of per-second atomic counters so reads never block writers.
+///
+/// # Fields
+/// * `buckets` - One counter per second in the window
+/// * `bucket_seconds` - The epoch second each bucket currently represents, used to detect and
+///   reset stale buckets as time moves forward
+struct TpsCounter {
+    buckets: Vec<AtomicU64>,
+    bucket_seconds: Vec<AtomicU64>,
+}
+
+impl TpsCounter {
+    fn new() -> Self {
+        TpsCounter {
+            buckets: (0..TPS_WINDOW_SECONDS).map(|_| AtomicU64::new(0)).collect(),
+            bucket_seconds: (0..TPS_WINDOW_SECONDS).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    /// Record a single ingested transaction against the current second's bucket
+    fn record(&self) {
+        let now = current_epoch_second();
+        let index = now as usize % TPS_WINDOW_SECONDS;
+
+        // Reset the bucket if it still holds a count from a previous pass through the ring.
+        if self.bucket_seconds[index].swap(now, Ordering::AcqRel) != now {
+            self.buckets[index].store(1, Ordering::Release);
+        } else {
+            self.buckets[index].fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    /// The mean transactions-per-second over the trailing window
+    fn mean_tps(&self) -> f64 {
+        let now = current_epoch_second();
+        let total: u64 = (0..TPS_WINDOW_SECONDS)
+            .filter(|&index| now.saturating_sub(self.bucket_seconds[index].load(Ordering::Acquire)) < TPS_WINDOW_SECONDS as u64)
+            .map(|index| self.buckets[index].load(Ordering::Acquire))
+            .sum();
+        total as f64 / TPS_WINDOW_SECONDS as f64
+    }
+
+    /// The peak transactions-per-second observed in any single second of the trailing window
+    fn peak_tps(&self) -> u64 {
+        let now = current_epoch_second();
+        (0..TPS_WINDOW_SECONDS)
+            .filter(|&index| now.saturating_sub(self.bucket_seconds[index].load(Ordering::Acquire)) < TPS_WINDOW_SECONDS as u64)
+            .map(|index| self.buckets[index].load(Ordering::Acquire))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// An exponential-bucket latency histogram
+///
+/// This struct tracks RPC round-trip latency in buckets spaced `1ms*2^k`, alongside a running
+/// total and count so quantiles can be derived by walking the buckets until the cumulative
+/// count crosses the target fraction.
+///
+/// # Fields
+/// * `buckets` - One counter per exponential bucket, counting observations `<=` its upper bound;
+///   the last bucket also counts every observation above its bound
+/// * `total_nanos` - The running sum of all observed latencies, in nanoseconds
+/// * `count` - The running count of all observations
+struct LatencyHistogram {
+    buckets: Vec<AtomicU64>,
+    total_nanos: AtomicU64,
+    count: AtomicU64,
+}
+
+impl LatencyHistogram {
+    fn new() -> Self {
+        LatencyHistogram {
+            buckets: (0..LATENCY_BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            total_nanos: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// The upper bound, in milliseconds, of bucket `index`
+    fn bucket_upper_bound_ms(index: usize) -> u64 {
+        1u64 << index
+    }
+
+    fn bucket_index(duration: Duration) -> usize {
+        // Round up to whole milliseconds so e.g. 1.5ms lands in the 2ms bucket, not the 1ms one.
+        let millis = ((duration.as_nanos() + 999_999) / 1_000_000).max(1) as u64;
+        (0..LATENCY_BUCKETS)
+            .find(|&index| millis <= Self::bucket_upper_bound_ms(index))
+            .unwrap_or(LATENCY_BUCKETS - 1)
+    }
+
+    fn record(&self, duration: Duration) {
+        let index = Self::bucket_index(duration);
+        self.buckets[index].fetch_add(1, Ordering::AcqRel);
+        self.total_nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::AcqRel);
+        self.count.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// The latency below which `quantile` (0.0-1.0) of observations fall
+    fn quantile(&self, quantile: f64) -> Duration {
+        let total = self.count.load(Ordering::Acquire);
+        if total == 0 {
+            return Duration::ZERO;
+        }
+
+        let target = (total as f64 * quantile).ceil() as u64;
+        let mut cumulative = 0u64;
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Acquire);
+            if cumulative >= target {
+                return Duration::from_millis(Self::bucket_upper_bound_ms(index));
+            }
+        }
+
+        Duration::from_millis(Self::bucket_upper_bound_ms(LATENCY_BUCKETS - 1))
+    }
+}
+
+/// Throughput and latency metrics
+///
+/// This struct is the aggregator's observability surface: a rolling ingestion-throughput
+/// counter and an RPC-latency histogram, exposed as Prometheus text and CSV rows.
+pub struct Metrics {
+    tps: TpsCounter,
+    get_transaction_latency: LatencyHistogram,
+}
+
+impl Metrics {
+    /// Create a new, empty metrics registry
+    pub fn new() -> Self {
+        Metrics {
+            tps: TpsCounter::new(),
+            get_transaction_latency: LatencyHistogram::new(),
+        }
+    }
+
+    /// Record that a transaction was ingested just now
+    pub fn record_ingested(&self) {
+        self.tps.record();
+    }
+
+    /// Time a `get_transaction` call and record its latency
+    ///
+    /// This function measures the wall-clock duration of `f` and records it in the
+    /// `get_transaction` latency histogram before returning its result.
+    pub fn time_get_transaction<T>(&self, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.get_transaction_latency.record(start.elapsed());
+        result
+    }
+
+    /// Render the current metrics in Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str("# HELP solana_tx_aggregator_tps_mean Mean ingested transactions per second over the trailing window.\n");
+        output.push_str("# TYPE solana_tx_aggregator_tps_mean gauge\n");
+        output.push_str(&format!(
+            "solana_tx_aggregator_tps_mean {}\n",
+            self.tps.mean_tps()
+        ));
+
+        output.push_str("# HELP solana_tx_aggregator_tps_peak Peak ingested transactions in any single second of the trailing window.\n");
+        output.push_str("# TYPE solana_tx_aggregator_tps_peak gauge\n");
+        output.push_str(&format!(
+            "solana_tx_aggregator_tps_peak {}\n",
+            self.tps.peak_tps()
+        ));
+
+        output.push_str("# HELP solana_tx_aggregator_get_transaction_latency_ms get_transaction round-trip latency quantiles, in milliseconds.\n");
+        output.push_str("# TYPE solana_tx_aggregator_get_transaction_latency_ms gauge\n");
+        for (label, quantile) in [("p50", 0.50), ("p90", 0.90), ("p99", 0.99)] {
+            output.push_str(&format!(
+                "solana_tx_aggregator_get_transaction_latency_ms{{quantile=\"{}\"}} {}\n",
+                label,
+                self.get_transaction_latency.quantile(quantile).as_millis()
+            ));
+        }
+
+        output.push_str("# HELP solana_tx_aggregator_get_transaction_total Total number of get_transaction calls observed.\n");
+        output.push_str("# TYPE solana_tx_aggregator_get_transaction_total counter\n");
+        output.push_str(&format!(
+            "solana_tx_aggregator_get_transaction_total {}\n",
+            self.get_transaction_latency.count.load(Ordering::Acquire)
+        ));
+
+        output
+    }
+
+    /// Render one CSV row: `timestamp,tps,p50,p99,total`
+    pub fn render_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{}",
+            current_epoch_second(),
+            self.tps.mean_tps(),
+            self.get_transaction_latency.quantile(0.50).as_millis(),
+            self.get_transaction_latency.quantile(0.99).as_millis(),
+            self.get_transaction_latency.count.load(Ordering::Acquire)
+        )
+    }
+
+    /// The CSV header matching `render_csv_row`
+    pub fn csv_header() -> &'static str {
+        "timestamp,tps,p50,p99,total"
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn current_epoch_second() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tps_counter_records_and_reports() {
+        let counter = TpsCounter::new();
+        counter.record();
+        counter.record();
+        counter.record();
+
+        assert_eq!(counter.peak_tps(), 3);
+        assert!(counter.mean_tps() > 0.0);
+    }
+
+    #[test]
+    fn test_latency_histogram_quantiles() {
+        let histogram = LatencyHistogram::new();
+        for millis in [1, 2, 4, 8, 16, 32] {
+            histogram.record(Duration::from_millis(millis));
+        }
+
+        assert!(histogram.quantile(0.50) <= Duration::from_millis(8));
+        assert!(histogram.quantile(0.99) >= Duration::from_millis(16));
+    }
+
+    #[test]
+    fn test_render_prometheus_contains_expected_metrics() {
+        let metrics = Metrics::new();
+        metrics.record_ingested();
+        metrics.time_get_transaction(|| {});
+
+        let rendered = metrics.render_prometheus();
+        assert!(rendered.contains("solana_tx_aggregator_tps_mean"));
+        assert!(rendered.contains("solana_tx_aggregator_get_transaction_latency_ms"));
+    }
+}
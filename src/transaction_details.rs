@@ -1,19 +1,47 @@
 use serde::{Deserialize, Serialize};
 
+/// A single instruction within a transaction
+///
+/// This struct represents one instruction from a transaction's message, covering both the
+/// compiled (`UiMessage::Raw`) and decoded (`UiMessage::Parsed`) representations.
+///
+/// # Fields
+/// * `program_id_index` - The index of the program id within the transaction's account keys
+/// * `accounts` - The account pubkeys referenced by this instruction, in order; empty when
+///   `parsed` is set
+/// * `data` - The raw, base58-encoded instruction data; empty when `parsed` is set
+/// * `parsed` - The decoded instruction payload, present when the RPC returned a parsed
+///   SPL-token or system instruction
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstructionDetails {
+    pub program_id_index: u8,
+    pub accounts: Vec<String>,
+    pub data: String,
+    pub parsed: Option<serde_json::Value>,
+}
+
 /// Transaction details
 ///
 /// This struct represents the details of a transaction.
 ///
 /// # Fields
-/// * `sender` - The sender of the transaction
-/// * `receiver` - The receiver of the transaction
-/// * `data` - The data of the transaction
+/// * `account_keys` - The full list of account pubkeys referenced by the transaction
+/// * `instructions` - Every instruction in the transaction's message
+/// * `fee` - The fee paid for the transaction, in lamports
+/// * `compute_units_consumed` - The number of compute units consumed, if reported
+/// * `success` - Whether the transaction executed without an error
+/// * `err` - The transaction error, if any
+/// * `log_messages` - The log messages emitted during execution
 /// * `timestamp` - The timestamp of the transaction
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TransactionDetails {
-    pub sender: String,
-    pub receiver: String,
-    pub data: String,
+    pub account_keys: Vec<String>,
+    pub instructions: Vec<InstructionDetails>,
+    pub fee: u64,
+    pub compute_units_consumed: Option<u64>,
+    pub success: bool,
+    pub err: Option<String>,
+    pub log_messages: Vec<String>,
     pub timestamp: i64,
 }
 
@@ -24,18 +52,28 @@ mod tests {
     #[test]
     fn test_transaction_details_serialization() {
         let transaction_details = TransactionDetails {
-            sender: "SenderPubkey".to_string(),
-            receiver: "ReceiverPubkey".to_string(),
-            data: "some_data".to_string(),
+            account_keys: vec!["SenderPubkey".to_string(), "ReceiverPubkey".to_string()],
+            instructions: vec![InstructionDetails {
+                program_id_index: 2,
+                accounts: vec!["SenderPubkey".to_string(), "ReceiverPubkey".to_string()],
+                data: "some_data".to_string(),
+                parsed: None,
+            }],
+            fee: 5000,
+            compute_units_consumed: Some(150),
+            success: true,
+            err: None,
+            log_messages: vec!["Program log: success".to_string()],
             timestamp: 1620000000,
         };
 
         let serialized = serde_json::to_string(&transaction_details).unwrap();
         let deserialized: TransactionDetails = serde_json::from_str(&serialized).unwrap();
 
-        assert_eq!(deserialized.sender, "SenderPubkey");
-        assert_eq!(deserialized.receiver, "ReceiverPubkey");
-        assert_eq!(deserialized.data, "some_data");
+        assert_eq!(deserialized.account_keys[0], "SenderPubkey");
+        assert_eq!(deserialized.instructions[0].data, "some_data");
+        assert_eq!(deserialized.fee, 5000);
+        assert!(deserialized.success);
         assert_eq!(deserialized.timestamp, 1620000000);
     }
 }